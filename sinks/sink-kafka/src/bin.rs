@@ -0,0 +1,42 @@
+use apibara_core::starknet::v1alpha2::{Block, Filter};
+use apibara_observability::init_opentelemetry;
+use apibara_sink_common::{ConfigurationArgs, SinkConnector, SinkConnectorExt};
+use apibara_sink_kafka::KafkaSink;
+use clap::Parser;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Comma-separated list of Kafka broker addresses.
+    #[arg(long, env)]
+    brokers: String,
+    /// Topic to publish messages to.
+    #[arg(long, env)]
+    topic: String,
+    /// Field in the transform output used to derive the partition key.
+    ///
+    /// Falls back to keying messages by block number when not set.
+    #[arg(long, env)]
+    partition_key: Option<String>,
+    #[arg(long, env, action)]
+    /// Send the data received from the transform step as is, instead of wrapping
+    /// it with cursor metadata.
+    raw: bool,
+    #[command(flatten)]
+    configuration: ConfigurationArgs,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    init_opentelemetry()?;
+    let args = Cli::parse();
+
+    let sink = KafkaSink::new(args.brokers, args.topic, args.raw)?.with_partition_key(args.partition_key);
+    let ct = CancellationToken::new();
+    let connector = SinkConnector::<Filter, Block>::from_configuration_args(args.configuration)?;
+
+    connector.consume_stream(sink, ct).await?;
+
+    Ok(())
+}
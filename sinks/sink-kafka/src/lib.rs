@@ -0,0 +1,129 @@
+//! Publish transformed data to a Kafka topic.
+
+use std::time::Duration;
+
+use apibara_core::starknet::v1alpha2::Cursor;
+use apibara_sink_common::{CursorAction, DataFinality, Sink};
+use async_trait::async_trait;
+use rdkafka::config::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use serde_json::Value;
+use tracing::{instrument, warn};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SinkKafkaError {
+    #[error("failed to configure kafka producer")]
+    Configuration,
+    #[error("failed to publish message to kafka")]
+    Publish,
+}
+
+pub struct KafkaSink {
+    producer: FutureProducer,
+    topic: String,
+    raw: bool,
+    partition_key: Option<String>,
+}
+
+impl KafkaSink {
+    pub fn new(brokers: String, topic: String, raw: bool) -> Result<Self, SinkKafkaError> {
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", &brokers)
+            .set("message.timeout.ms", "5000")
+            .create()
+            .map_err(|_| SinkKafkaError::Configuration)?;
+
+        Ok(KafkaSink {
+            producer,
+            topic,
+            raw,
+            partition_key: None,
+        })
+    }
+
+    /// Set the field used to derive the partition key for each message.
+    ///
+    /// When not set, messages are keyed by the end cursor's block number so that a
+    /// given block always lands on the same partition.
+    pub fn with_partition_key(mut self, partition_key: Option<String>) -> Self {
+        self.partition_key = partition_key;
+        self
+    }
+
+    fn partition_key_for(&self, end_cursor: &Cursor, value: &Value) -> String {
+        if let Some(field) = &self.partition_key {
+            if let Some(extracted) = value.get(field) {
+                return extracted
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| extracted.to_string());
+            }
+        }
+        end_cursor.order_key.to_string()
+    }
+
+    async fn publish(&self, key: Option<&str>, payload: &[u8]) -> Result<(), SinkKafkaError> {
+        let mut record = FutureRecord::to(&self.topic).payload(payload);
+        if let Some(key) = key {
+            record = record.key(key);
+        }
+
+        self.producer
+            .send(record, Duration::from_secs(5))
+            .await
+            .map_err(|(err, _)| {
+                warn!(error = ?err, "kafka publish failed");
+                SinkKafkaError::Publish
+            })?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Sink for KafkaSink {
+    type Options = ();
+    type Error = SinkKafkaError;
+
+    #[instrument(skip_all, err(Debug))]
+    async fn handle_data(
+        &mut self,
+        _cursor: &Option<Cursor>,
+        end_cursor: &Cursor,
+        _finality: &DataFinality,
+        batch: &Value,
+    ) -> Result<CursorAction, Self::Error> {
+        let values = match batch.as_array() {
+            Some(values) => values.clone(),
+            None => vec![batch.clone()],
+        };
+
+        for value in values {
+            let key = self.partition_key_for(end_cursor, &value);
+            let payload = if self.raw {
+                value
+            } else {
+                serde_json::json!({
+                    "cursor": end_cursor,
+                    "data": value,
+                })
+            };
+            let payload =
+                serde_json::to_vec(&payload).map_err(|_| SinkKafkaError::Publish)?;
+            self.publish(Some(&key), &payload).await?;
+        }
+
+        Ok(CursorAction::Persist)
+    }
+
+    #[instrument(skip_all, err(Debug))]
+    async fn handle_invalidate(&mut self, cursor: &Option<Cursor>) -> Result<(), Self::Error> {
+        let invalidated_after = cursor.as_ref().map(|cursor| cursor.order_key);
+        let tombstone = serde_json::json!({
+            "_tombstone": true,
+            "invalidated_after": invalidated_after,
+        });
+        let payload = serde_json::to_vec(&tombstone).map_err(|_| SinkKafkaError::Publish)?;
+        self.publish(None, &payload).await
+    }
+}
@@ -0,0 +1,165 @@
+//! Upsert transformed data into a PostgreSQL table, reconciling chain reorgs.
+
+use apibara_core::starknet::v1alpha2::Cursor;
+use apibara_sink_common::{CursorAction, DataFinality, Sink};
+use async_trait::async_trait;
+use serde_json::Value;
+use tokio_postgres::{Client, NoTls};
+use tracing::instrument;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SinkPostgresError {
+    #[error("failed to connect to postgres")]
+    Connection,
+    #[error("failed to run query")]
+    Query(#[from] tokio_postgres::Error),
+}
+
+pub struct PostgresSink {
+    client: Client,
+    /// Double-quoted, escaped identifier for the data table.
+    table_name: String,
+    /// Double-quoted, escaped identifier for the checkpoint table.
+    checkpoint_table_name: String,
+}
+
+/// Quote and escape a user-provided name so it's safe to splice into a SQL
+/// identifier position, e.g. `my"table` becomes `"my""table"`.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+impl PostgresSink {
+    pub async fn new(connection_string: String, table_name: String) -> Result<Self, SinkPostgresError> {
+        let (client, connection) = tokio_postgres::connect(&connection_string, NoTls)
+            .await
+            .map_err(|_| SinkPostgresError::Connection)?;
+
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                tracing::error!(error = ?err, "postgres connection error");
+            }
+        });
+
+        let checkpoint_table_name = quote_identifier(&format!("{table_name}_checkpoint"));
+        let table_name = quote_identifier(&table_name);
+
+        client
+            .batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS {table_name} (
+                    block_number BIGINT NOT NULL,
+                    insert_index INTEGER NOT NULL,
+                    data JSONB NOT NULL,
+                    PRIMARY KEY (block_number, insert_index)
+                );
+                CREATE TABLE IF NOT EXISTS {checkpoint_table_name} (
+                    id BOOLEAN PRIMARY KEY DEFAULT true,
+                    order_key BIGINT NOT NULL,
+                    CHECK (id)
+                );",
+            ))
+            .await?;
+
+        Ok(PostgresSink {
+            client,
+            table_name,
+            checkpoint_table_name,
+        })
+    }
+}
+
+#[async_trait]
+impl Sink for PostgresSink {
+    type Options = ();
+    type Error = SinkPostgresError;
+
+    #[instrument(skip_all, err(Debug))]
+    async fn handle_data(
+        &mut self,
+        _cursor: &Option<Cursor>,
+        end_cursor: &Cursor,
+        _finality: &DataFinality,
+        batch: &Value,
+    ) -> Result<CursorAction, Self::Error> {
+        let values = match batch.as_array() {
+            Some(values) => values.clone(),
+            None => vec![batch.clone()],
+        };
+
+        let transaction = self.client.transaction().await?;
+
+        // `insert_index` makes each row in the batch addressable by (cursor,
+        // position), so redelivering the same batch after a retry upserts the
+        // same rows instead of duplicating them.
+        for (insert_index, value) in values.into_iter().enumerate() {
+            transaction
+                .execute(
+                    &format!(
+                        "INSERT INTO {} (block_number, insert_index, data) VALUES ($1, $2, $3)
+                         ON CONFLICT (block_number, insert_index) DO UPDATE SET data = excluded.data",
+                        self.table_name
+                    ),
+                    &[&(end_cursor.order_key as i64), &(insert_index as i32), &value],
+                )
+                .await?;
+        }
+
+        transaction
+            .execute(
+                &format!(
+                    "INSERT INTO {0} (id, order_key) VALUES (true, $1)
+                     ON CONFLICT (id) DO UPDATE SET order_key = excluded.order_key",
+                    self.checkpoint_table_name
+                ),
+                &[&(end_cursor.order_key as i64)],
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(CursorAction::Persist)
+    }
+
+    #[instrument(skip_all, err(Debug))]
+    async fn handle_invalidate(&mut self, cursor: &Option<Cursor>) -> Result<(), Self::Error> {
+        let invalidated_after = cursor.as_ref().map(|cursor| cursor.order_key).unwrap_or(0) as i64;
+
+        let transaction = self.client.transaction().await?;
+
+        transaction
+            .execute(
+                &format!(
+                    "DELETE FROM {} WHERE block_number > $1",
+                    self.table_name
+                ),
+                &[&invalidated_after],
+            )
+            .await?;
+
+        transaction
+            .execute(
+                &format!(
+                    "INSERT INTO {0} (id, order_key) VALUES (true, $1)
+                     ON CONFLICT (id) DO UPDATE SET order_key = excluded.order_key",
+                    self.checkpoint_table_name
+                ),
+                &[&invalidated_after],
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_identifier_escapes_embedded_quotes() {
+        assert_eq!(quote_identifier("blocks"), "\"blocks\"");
+        assert_eq!(quote_identifier("my\"table"), "\"my\"\"table\"");
+    }
+}
@@ -0,0 +1,23 @@
+//! Identifies a block by number and hash on a specific chain.
+
+/// A block number together with its hash, uniquely identifying a block even
+/// across a reorg that replaces the block at that number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlobalBlockId {
+    number: u64,
+    hash: [u8; 32],
+}
+
+impl GlobalBlockId {
+    pub fn new(number: u64, hash: [u8; 32]) -> Self {
+        GlobalBlockId { number, hash }
+    }
+
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn hash(&self) -> &[u8; 32] {
+        &self.hash
+    }
+}
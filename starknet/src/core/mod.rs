@@ -0,0 +1,8 @@
+//! Core types shared by the starknet data stream: protobuf-shaped messages and
+//! block identifiers.
+
+pub mod pb;
+
+mod global_block_id;
+
+pub use global_block_id::GlobalBlockId;
@@ -0,0 +1,416 @@
+//! Starknet block data and the filters clients use to select what they want
+//! streamed back to them.
+
+/// A 252-bit Starknet field element, e.g. a contract address or storage key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FieldElement(pub [u8; 32]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    pub seconds: i64,
+    pub nanos: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockStatus {
+    #[default]
+    Unspecified,
+    Pending,
+    AcceptedOnL2,
+    AcceptedOnL1,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BlockHeader {
+    pub block_hash: Option<FieldElement>,
+    pub timestamp: Option<Timestamp>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Transaction {
+    pub transaction_type: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Event {
+    pub from_address: Option<FieldElement>,
+    pub keys: Vec<FieldElement>,
+    pub data: Vec<FieldElement>,
+}
+
+/// An L1<->L2 message. The filters in this module only care whether a receipt
+/// has any, so the message content isn't modeled here.
+#[derive(Debug, Clone, Default)]
+pub struct MsgToL1;
+#[derive(Debug, Clone, Default)]
+pub struct MsgToL2;
+
+#[derive(Debug, Clone, Default)]
+pub struct TransactionReceipt {
+    pub transaction_type: i32,
+    pub revert_reason: Option<String>,
+    pub events: Vec<Event>,
+    pub l1_to_l2_messages: Vec<MsgToL2>,
+    pub l2_to_l1_messages: Vec<MsgToL1>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StorageEntry {
+    pub key: Option<FieldElement>,
+    pub value: Option<FieldElement>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StorageDiff {
+    pub contract_address: Option<FieldElement>,
+    pub storage_entries: Vec<StorageEntry>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NonceUpdate {
+    pub contract_address: Option<FieldElement>,
+    pub nonce: Option<FieldElement>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeclaredClass;
+#[derive(Debug, Clone, Default)]
+pub struct DeployedContract;
+#[derive(Debug, Clone, Default)]
+pub struct ReplacedClass;
+
+#[derive(Debug, Clone, Default)]
+pub struct StateDiff {
+    pub storage_diffs: Vec<StorageDiff>,
+    pub nonces: Vec<NonceUpdate>,
+    pub declared_classes: Vec<DeclaredClass>,
+    pub deprecated_declared_classes: Vec<FieldElement>,
+    pub deployed_contracts: Vec<DeployedContract>,
+    pub replaced_classes: Vec<ReplacedClass>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StateUpdate {
+    pub new_root: Option<FieldElement>,
+    pub old_root: Option<FieldElement>,
+    pub state_diff: Option<StateDiff>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Block {
+    pub status: i32,
+    pub header: Option<BlockHeader>,
+    pub state_update: Option<StateUpdate>,
+    pub transactions: Vec<Transaction>,
+    pub receipts: Vec<TransactionReceipt>,
+    pub events: Vec<Event>,
+}
+
+impl Block {
+    pub fn status(&self) -> BlockStatus {
+        match self.status {
+            1 => BlockStatus::Pending,
+            2 => BlockStatus::AcceptedOnL2,
+            3 => BlockStatus::AcceptedOnL1,
+            4 => BlockStatus::Rejected,
+            _ => BlockStatus::Unspecified,
+        }
+    }
+}
+
+/// Matches every header (there's nothing to filter on yet beyond requesting it
+/// or not).
+#[derive(Debug, Clone, Default)]
+pub struct HeaderFilter;
+
+#[derive(Debug, Clone, Default)]
+pub struct TransactionFilter {
+    pub transaction_type: Option<i32>,
+}
+
+impl TransactionFilter {
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        match self.transaction_type {
+            Some(transaction_type) => transaction.transaction_type == transaction_type,
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub from_address: Option<FieldElement>,
+    pub keys: Vec<FieldElement>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &Event) -> bool {
+        if let Some(from_address) = &self.from_address {
+            if event.from_address.as_ref() != Some(from_address) {
+                return false;
+            }
+        }
+
+        self.keys.iter().zip(event.keys.iter()).all(|(want, got)| want == got)
+    }
+}
+
+/// Matches transaction receipts by type, revert status, an emitted event key, or
+/// the presence of an L1/L2 message, independent of which transactions were also
+/// requested.
+///
+/// An empty set of conditions matches every receipt.
+#[derive(Debug, Clone, Default)]
+pub struct ReceiptFilter {
+    /// Only match receipts belonging to a transaction of this type.
+    pub transaction_type: Option<i32>,
+    /// Only match receipts that reverted (`Some(true)`) or succeeded
+    /// (`Some(false)`).
+    pub reverted: Option<bool>,
+    /// Only match receipts with an emitted event whose first key equals this
+    /// value.
+    pub event_key: Option<FieldElement>,
+    /// Only match receipts that emitted at least one L1 or L2 message
+    /// (`Some(true)`) or none at all (`Some(false)`).
+    pub with_messages: Option<bool>,
+}
+
+impl ReceiptFilter {
+    pub fn matches(&self, receipt: &TransactionReceipt) -> bool {
+        if let Some(transaction_type) = self.transaction_type {
+            if receipt.transaction_type != transaction_type {
+                return false;
+            }
+        }
+
+        if let Some(reverted) = self.reverted {
+            if receipt.revert_reason.is_some() != reverted {
+                return false;
+            }
+        }
+
+        if let Some(event_key) = &self.event_key {
+            let has_key = receipt
+                .events
+                .iter()
+                .any(|event| event.keys.first() == Some(event_key));
+            if !has_key {
+                return false;
+            }
+        }
+
+        if let Some(with_messages) = self.with_messages {
+            let has_messages =
+                !receipt.l1_to_l2_messages.is_empty() || !receipt.l2_to_l1_messages.is_empty();
+            if has_messages != with_messages {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Matches individual storage entries by contract address and/or storage key.
+/// An unset field matches anything; an empty `StateUpdateFilter::storage_diffs`
+/// keeps every entry.
+#[derive(Debug, Clone, Default)]
+pub struct StorageDiffFilter {
+    pub contract_address: Option<FieldElement>,
+    pub storage_key: Option<FieldElement>,
+}
+
+impl StorageDiffFilter {
+    fn matches(&self, contract_address: Option<&FieldElement>, entry: &StorageEntry) -> bool {
+        if let Some(filter_address) = &self.contract_address {
+            if contract_address != Some(filter_address) {
+                return false;
+            }
+        }
+
+        if let Some(storage_key) = &self.storage_key {
+            if entry.key.as_ref() != Some(storage_key) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Matches nonce updates by contract address.
+#[derive(Debug, Clone, Default)]
+pub struct NonceUpdateFilter {
+    pub contract_address: Option<FieldElement>,
+}
+
+impl NonceUpdateFilter {
+    fn matches(&self, update: &NonceUpdate) -> bool {
+        match &self.contract_address {
+            Some(contract_address) => update.contract_address.as_ref() == Some(contract_address),
+            None => true,
+        }
+    }
+}
+
+/// Selects which parts of a block's state update to keep.
+///
+/// `storage_diffs` and `nonce_updates` prune down to individual entries, not
+/// whole per-contract diffs: an empty list keeps every entry of that kind, a
+/// non-empty one keeps only the entries matched by at least one of its filters.
+#[derive(Debug, Clone, Default)]
+pub struct StateUpdateFilter {
+    pub storage_diffs: Vec<StorageDiffFilter>,
+    pub nonce_updates: Vec<NonceUpdateFilter>,
+    pub declared_classes: bool,
+    pub deployed_and_replaced_contracts: bool,
+}
+
+impl StateUpdateFilter {
+    /// Prune `state_update` down to only the entries this filter matches,
+    /// returning `None` if nothing matched.
+    pub fn prune(&self, mut state_update: StateUpdate) -> Option<StateUpdate> {
+        let diff = state_update.state_diff.as_mut()?;
+
+        diff.storage_diffs.retain_mut(|storage_diff| {
+            if !self.storage_diffs.is_empty() {
+                let contract_address = storage_diff.contract_address;
+                storage_diff.storage_entries.retain(|entry| {
+                    self.storage_diffs
+                        .iter()
+                        .any(|filter| filter.matches(contract_address.as_ref(), entry))
+                });
+            }
+            !storage_diff.storage_entries.is_empty()
+        });
+
+        diff.nonces.retain(|entry| {
+            self.nonce_updates.is_empty()
+                || self
+                    .nonce_updates
+                    .iter()
+                    .any(|filter| filter.matches(entry))
+        });
+        if !self.declared_classes {
+            diff.declared_classes.clear();
+            diff.deprecated_declared_classes.clear();
+        }
+        if !self.deployed_and_replaced_contracts {
+            diff.deployed_contracts.clear();
+            diff.replaced_classes.clear();
+        }
+
+        let is_empty = diff.storage_diffs.is_empty()
+            && diff.nonces.is_empty()
+            && diff.declared_classes.is_empty()
+            && diff.deprecated_declared_classes.is_empty()
+            && diff.deployed_contracts.is_empty()
+            && diff.replaced_classes.is_empty();
+
+        if is_empty {
+            None
+        } else {
+            Some(state_update)
+        }
+    }
+}
+
+/// Selects which parts of block data a client wants streamed back to it.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub header: Option<HeaderFilter>,
+    pub transactions: Vec<TransactionFilter>,
+    pub events: Vec<EventFilter>,
+    pub receipts: Vec<ReceiptFilter>,
+    pub state_update: Option<StateUpdateFilter>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_element(byte: u8) -> FieldElement {
+        let mut bytes = [0u8; 32];
+        bytes[31] = byte;
+        FieldElement(bytes)
+    }
+
+    fn storage_entry(key: u8, value: u8) -> StorageEntry {
+        StorageEntry {
+            key: Some(field_element(key)),
+            value: Some(field_element(value)),
+        }
+    }
+
+    fn state_update_with_storage(contract: u8, entries: Vec<StorageEntry>) -> StateUpdate {
+        StateUpdate {
+            new_root: None,
+            old_root: None,
+            state_diff: Some(StateDiff {
+                storage_diffs: vec![StorageDiff {
+                    contract_address: Some(field_element(contract)),
+                    storage_entries: entries,
+                }],
+                nonces: Vec::new(),
+                declared_classes: Vec::new(),
+                deprecated_declared_classes: Vec::new(),
+                deployed_contracts: Vec::new(),
+                replaced_classes: Vec::new(),
+            }),
+        }
+    }
+
+    #[test]
+    fn prune_keeps_only_the_requested_storage_key() {
+        let state_update = state_update_with_storage(
+            1,
+            vec![storage_entry(1, 10), storage_entry(2, 20), storage_entry(3, 30)],
+        );
+
+        let filter = StateUpdateFilter {
+            storage_diffs: vec![StorageDiffFilter {
+                contract_address: Some(field_element(1)),
+                storage_key: Some(field_element(2)),
+            }],
+            ..StateUpdateFilter::default()
+        };
+
+        let pruned = filter.prune(state_update).expect("one entry matches");
+        let diff = &pruned.state_diff.unwrap().storage_diffs[0];
+        assert_eq!(diff.storage_entries.len(), 1);
+        assert_eq!(diff.storage_entries[0].key, Some(field_element(2)));
+    }
+
+    #[test]
+    fn prune_returns_none_when_nothing_matches() {
+        let state_update = state_update_with_storage(1, vec![storage_entry(1, 10)]);
+
+        let filter = StateUpdateFilter {
+            storage_diffs: vec![StorageDiffFilter {
+                contract_address: Some(field_element(2)),
+                storage_key: None,
+            }],
+            ..StateUpdateFilter::default()
+        };
+
+        assert!(filter.prune(state_update).is_none());
+    }
+
+    #[test]
+    fn receipt_filter_matches_on_revert_status() {
+        let reverted = TransactionReceipt {
+            revert_reason: Some("out of gas".to_string()),
+            ..TransactionReceipt::default()
+        };
+        let succeeded = TransactionReceipt::default();
+
+        let filter = ReceiptFilter {
+            reverted: Some(true),
+            ..ReceiptFilter::default()
+        };
+
+        assert!(filter.matches(&reverted));
+        assert!(!filter.matches(&succeeded));
+    }
+}
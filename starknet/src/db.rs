@@ -0,0 +1,37 @@
+//! Read access to locally stored Starknet block data.
+
+use crate::core::{pb::starknet::v1alpha2, GlobalBlockId};
+
+/// Read-only access to canonical chain data, backing the aggregators in
+/// [`crate::stream`].
+///
+/// Implementations are expected to be cheap to call repeatedly and safe to
+/// share across threads (callers like
+/// [`DatabaseBlockDataAggregator::aggregate_batch_concurrent`](crate::stream::aggregate::DatabaseBlockDataAggregator::aggregate_batch_concurrent)
+/// read from multiple worker threads at once).
+pub trait StorageReader: Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// The canonical block id for `number`, or `None` if no canonical block
+    /// has been assigned that number yet.
+    fn canonical_block_id(&self, number: u64) -> Result<Option<GlobalBlockId>, Self::Error>;
+
+    /// The highest block number with a canonical block id.
+    fn highest_accepted_block_number(&self) -> Result<u64, Self::Error>;
+
+    fn read_status(&self, block_id: &GlobalBlockId) -> Result<Option<v1alpha2::BlockStatus>, Self::Error>;
+
+    fn read_header(&self, block_id: &GlobalBlockId) -> Result<Option<v1alpha2::BlockHeader>, Self::Error>;
+
+    fn read_body(&self, block_id: &GlobalBlockId) -> Result<Vec<v1alpha2::Transaction>, Self::Error>;
+
+    fn read_receipts(
+        &self,
+        block_id: &GlobalBlockId,
+    ) -> Result<Vec<v1alpha2::TransactionReceipt>, Self::Error>;
+
+    fn read_state_update(
+        &self,
+        block_id: &GlobalBlockId,
+    ) -> Result<Option<v1alpha2::StateUpdate>, Self::Error>;
+}
@@ -0,0 +1,71 @@
+//! Drive a historical backfill using the parallel aggregation path.
+
+use crate::{
+    core::{pb::starknet::v1alpha2, GlobalBlockId},
+    db::StorageReader,
+};
+
+use super::{
+    aggregate::{DatabaseBlockDataAggregator, DEFAULT_AGGREGATE_CONCURRENCY},
+    configuration::StreamStartingPoint,
+};
+
+/// How many blocks to request per batch and how many of those to fetch in
+/// parallel. Larger values trade memory for throughput.
+#[derive(Debug, Clone, Copy)]
+pub struct BackfillOptions {
+    pub batch_size: usize,
+    pub concurrency: usize,
+}
+
+impl Default for BackfillOptions {
+    fn default() -> Self {
+        BackfillOptions {
+            batch_size: 100,
+            concurrency: DEFAULT_AGGREGATE_CONCURRENCY,
+        }
+    }
+}
+
+/// Aggregate every matching block from `starting_point` onwards, calling
+/// `on_batch` with each non-empty batch as it's produced.
+///
+/// Stops as soon as a batch comes back smaller than `options.batch_size`, which
+/// is what `aggregate_batch_concurrent` returns once it hits a block with no
+/// data, a status different from the requested one, or the end of the
+/// canonical chain. Returns the cursor to
+/// resume from, or `None` if `starting_point` is a timestamp that hasn't been
+/// produced yet.
+pub fn backfill<R>(
+    aggregator: &DatabaseBlockDataAggregator<R>,
+    starting_point: StreamStartingPoint,
+    status: v1alpha2::BlockStatus,
+    options: BackfillOptions,
+    mut on_batch: impl FnMut(Vec<v1alpha2::Block>),
+) -> Result<Option<GlobalBlockId>, R::Error>
+where
+    R: StorageReader + Sync,
+    R::Error: Send,
+{
+    let Some(mut cursor) = aggregator.resolve_starting_point(&starting_point)? else {
+        return Ok(None);
+    };
+    loop {
+        let (blocks, next_cursor) = aggregator.aggregate_batch_concurrent(
+            &cursor,
+            options.batch_size,
+            status,
+            options.concurrency,
+        )?;
+
+        let got_full_batch = blocks.len() == options.batch_size;
+        if !blocks.is_empty() {
+            on_batch(blocks);
+        }
+        cursor = next_cursor;
+
+        if !got_full_batch {
+            return Ok(Some(cursor));
+        }
+    }
+}
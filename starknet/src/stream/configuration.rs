@@ -0,0 +1,38 @@
+//! Where a stream should start from, as configured by the caller (CLI flag or
+//! script configuration).
+
+use crate::{core::GlobalBlockId, db::StorageReader};
+
+use super::starting_block::{resolve_starting_block_by_timestamp, StartingBlock};
+
+/// User-provided starting point for a data stream: either a specific block, or a
+/// timestamp to be resolved to the first canonical block produced at or after it.
+#[derive(Debug, Clone, Copy)]
+pub enum StreamStartingPoint {
+    /// Start at this exact block.
+    Block(GlobalBlockId),
+    /// Start at the earliest canonical block at or after this timestamp (seconds
+    /// since the Unix epoch).
+    Timestamp(u64),
+}
+
+impl StreamStartingPoint {
+    /// Resolve this starting point to a concrete block to begin streaming from.
+    ///
+    /// Returns `Ok(None)` when a `Timestamp` target hasn't been produced yet, so
+    /// the connector can wait for more blocks instead of erroring.
+    pub fn resolve<R>(&self, storage: &R) -> Result<Option<GlobalBlockId>, R::Error>
+    where
+        R: StorageReader,
+    {
+        match self {
+            StreamStartingPoint::Block(block_id) => Ok(Some(*block_id)),
+            StreamStartingPoint::Timestamp(target_timestamp_seconds) => {
+                match resolve_starting_block_by_timestamp(storage, *target_timestamp_seconds)? {
+                    StartingBlock::Block(block_id) => Ok(Some(block_id)),
+                    StartingBlock::NotYetProduced => Ok(None),
+                }
+            }
+        }
+    }
+}
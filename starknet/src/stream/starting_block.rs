@@ -0,0 +1,154 @@
+//! Resolve a starting cursor from a target timestamp instead of a block number.
+
+use crate::{core::GlobalBlockId, db::StorageReader};
+
+/// Result of resolving a starting block from a timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartingBlock {
+    /// The earliest canonical block whose header timestamp is `>=` the target.
+    Block(GlobalBlockId),
+    /// The target timestamp is beyond the chain tip; the caller should wait for
+    /// more blocks to be produced instead of erroring.
+    NotYetProduced,
+}
+
+/// Binary search the canonical chain for the earliest block at or after
+/// `target_timestamp_seconds`.
+///
+/// Block numbers with no canonical block id or missing header (e.g. a gap left by
+/// a past reorg) are treated the same as "too early" and skipped over. A target
+/// earlier than genesis resolves to block 0.
+pub fn resolve_starting_block_by_timestamp<R>(
+    storage: &R,
+    target_timestamp_seconds: u64,
+) -> Result<StartingBlock, R::Error>
+where
+    R: StorageReader,
+{
+    let mut low = 0u64;
+    let mut high = storage.highest_accepted_block_number()?;
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match header_timestamp_at(storage, mid)? {
+            Some(timestamp) if timestamp >= target_timestamp_seconds => high = mid,
+            _ => low = mid + 1,
+        }
+    }
+
+    match header_timestamp_at(storage, low)? {
+        Some(timestamp) if timestamp >= target_timestamp_seconds => {
+            let block_id = storage
+                .canonical_block_id(low)?
+                .expect("canonical block id must exist for a block with a header");
+            Ok(StartingBlock::Block(block_id))
+        }
+        _ => Ok(StartingBlock::NotYetProduced),
+    }
+}
+
+fn header_timestamp_at<R>(storage: &R, number: u64) -> Result<Option<u64>, R::Error>
+where
+    R: StorageReader,
+{
+    let Some(block_id) = storage.canonical_block_id(number)? else {
+        return Ok(None);
+    };
+    let Some(header) = storage.read_header(&block_id)? else {
+        return Ok(None);
+    };
+    Ok(header.timestamp.map(|timestamp| timestamp.seconds as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::core::pb::starknet::v1alpha2;
+
+    use super::*;
+
+    /// A fixed chain of blocks numbered from 0, one per entry of `timestamps`,
+    /// each with that entry as its header timestamp.
+    struct FakeStorage {
+        timestamps: Vec<u64>,
+    }
+
+    fn block_id(number: u64) -> GlobalBlockId {
+        GlobalBlockId::new(number, [0u8; 32])
+    }
+
+    impl StorageReader for FakeStorage {
+        type Error = Infallible;
+
+        fn canonical_block_id(&self, number: u64) -> Result<Option<GlobalBlockId>, Self::Error> {
+            Ok((number < self.timestamps.len() as u64).then(|| block_id(number)))
+        }
+
+        fn highest_accepted_block_number(&self) -> Result<u64, Self::Error> {
+            Ok(self.timestamps.len() as u64 - 1)
+        }
+
+        fn read_status(
+            &self,
+            _block_id: &GlobalBlockId,
+        ) -> Result<Option<v1alpha2::BlockStatus>, Self::Error> {
+            Ok(Some(v1alpha2::BlockStatus::AcceptedOnL2))
+        }
+
+        fn read_header(
+            &self,
+            block_id: &GlobalBlockId,
+        ) -> Result<Option<v1alpha2::BlockHeader>, Self::Error> {
+            Ok(self.timestamps.get(block_id.number() as usize).map(|seconds| {
+                v1alpha2::BlockHeader {
+                    block_hash: None,
+                    timestamp: Some(v1alpha2::Timestamp {
+                        seconds: *seconds as i64,
+                        nanos: 0,
+                    }),
+                }
+            }))
+        }
+
+        fn read_body(&self, _block_id: &GlobalBlockId) -> Result<Vec<v1alpha2::Transaction>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        fn read_receipts(
+            &self,
+            _block_id: &GlobalBlockId,
+        ) -> Result<Vec<v1alpha2::TransactionReceipt>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        fn read_state_update(
+            &self,
+            _block_id: &GlobalBlockId,
+        ) -> Result<Option<v1alpha2::StateUpdate>, Self::Error> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn resolves_to_the_earliest_block_at_or_after_the_target() {
+        let storage = FakeStorage {
+            timestamps: vec![10, 20, 30, 40],
+        };
+
+        let resolved = resolve_starting_block_by_timestamp(&storage, 25).unwrap();
+        assert_eq!(resolved, StartingBlock::Block(block_id(2)));
+    }
+
+    #[test]
+    fn a_target_past_the_chain_tip_is_not_yet_produced() {
+        // The chain only has timestamps up to 40; a target beyond that is a gap
+        // at the tip that hasn't been produced yet, not an error.
+        let storage = FakeStorage {
+            timestamps: vec![10, 20, 30, 40],
+        };
+
+        let resolved = resolve_starting_block_by_timestamp(&storage, 1000).unwrap();
+        assert_eq!(resolved, StartingBlock::NotYetProduced);
+    }
+}